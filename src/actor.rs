@@ -1,6 +1,10 @@
-use std::{path::PathBuf, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
-use openssl::ssl::{Ssl, SslConnector, SslFiletype, SslMethod, SslVerifyMode};
+use openssl::{
+    pkey::PKey,
+    ssl::{Ssl, SslConnector, SslFiletype, SslMethod, SslVerifyMode},
+    x509::X509,
+};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::TcpStream,
@@ -9,7 +13,7 @@ use tokio_openssl::SslStream;
 use url::Url;
 use wildmatch::WildMatch;
 
-use crate::{error::ActorError, Response, UserAgent};
+use crate::{error::ActorError, known_hosts, Identity, Response, UserAgent};
 
 /// 🎠 An ergonomic way to call [`Actor::get`] with the default actor.
 ///
@@ -23,6 +27,11 @@ pub async fn trot(url: impl Into<String>) -> Result<Response> {
 
 /// 🎠 An ergonomic way to call [`Actor::input`] with the default actor.
 ///
+/// Use this to answer a `1x` prompt: check [`Response::input_prompt`] on the
+/// response you got back, and pass the user's answer (and the same url) here
+/// to re-request with it. `input` is percent-encoded and replaces any
+/// existing query on `url`.
+///
 /// ```
 /// Actor::trot_in("localhost/input", "notice me!").await
 /// ```
@@ -30,13 +39,72 @@ pub async fn trot_in(url: impl Into<String>, input: impl Into<String>) -> Result
     Actor::default().input(url.into(), input.into()).await
 }
 
+/// 🎠 An ergonomic way to call [`Actor::get`] with the default actor, but
+/// with an explicit redirect-hop limit instead of [`Actor::default`]'s.
+/// Pass `0` to get the raw `3x` response back instead of following it.
+pub async fn trot_follow(url: impl Into<String>, max_redirects: u8) -> Result<Response> {
+    Actor::default()
+        .follow_redirects(max_redirects)
+        .get(url)
+        .await
+}
+
 /// Make a gemini request.
 pub struct Actor {
-    pub cert:       Option<PathBuf>,
-    pub key:        Option<PathBuf>,
-    pub user_agent: Option<UserAgent>,
+    pub cert:        Option<PathBuf>,
+    pub key:         Option<PathBuf>,
+    pub user_agent:  Option<UserAgent>,
     /// Timeout for establishing tcp connections (default is 5 secs)
-    pub timeout:    Duration,
+    pub timeout:     Duration,
+    /// Path to a TOFU (trust-on-first-use) known-hosts file mapping
+    /// `host:port` to a pinned certificate fingerprint. When set, every
+    /// connection is pinned on first use and a later connection presenting a
+    /// different certificate is rejected with
+    /// [`ActorError::CertificateChanged`], unless the pinned certificate has
+    /// expired, in which case the pin is replaced. Left unset (the default), no
+    /// pinning is done and any certificate accepted by [`Actor::get`]'s
+    /// domain check is trusted, as before.
+    pub known_hosts:   Option<PathBuf>,
+    /// How many `3x` redirects [`Actor::get`]/[`Actor::input`] will
+    /// transparently follow before giving up with
+    /// [`ActorError::TooManyRedirects`] (default 5). Set to 0 to disable
+    /// redirect-following entirely.
+    pub max_redirects:     u8,
+    /// Deadline for the TLS handshake, the header read and the body read,
+    /// each timed separately (default 30 secs). Unlike [`Actor::timeout`],
+    /// which only bounds the initial tcp connect, this keeps a slow or
+    /// malicious server from hanging a request indefinitely once connected.
+    pub read_timeout:      Duration,
+    /// Cap on how many bytes of response body [`Actor::send_request`] will
+    /// buffer before giving up with [`ActorError::ResponseTooLarge`].
+    /// `None` (the default) means unbounded.
+    pub max_response_size: Option<usize>,
+    /// An in-memory client identity (see [`Identity`]) to present instead of
+    /// [`Actor::cert`]/[`Actor::key`]. Takes precedence over them when set.
+    pub identity:          Option<Identity>,
+    /// Choose a client identity per-host, for servers that respond `60`
+    /// (client certificate required) to some resources but not others.
+    /// Called with the request's domain; only consulted when
+    /// [`Actor::identity`] isn't set, and itself falls back to
+    /// [`Actor::cert`]/[`Actor::key`] if it returns `None`.
+    pub identity_for_host: Option<Arc<dyn Fn(&str) -> Option<Identity> + Send + Sync>>,
+    /// Opt-in automatic backoff for `44` (slow down) responses: when set, a
+    /// `44` is transparently retried - sleeping for [`Response::retry_after`]
+    /// (capped at [`RateLimitConfig::max_wait`]) each time - up to
+    /// [`RateLimitConfig::max_retries`] times, before giving up with
+    /// [`ActorError::RateLimited`]. Left unset (the default), a `44` is just
+    /// returned to the caller like any other response.
+    pub rate_limit:        Option<RateLimitConfig>,
+}
+
+/// See [`Actor::rate_limit`].
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    /// Upper bound on how long to sleep for a single `44`, regardless of
+    /// what the server's `meta` asks for.
+    pub max_wait: Duration,
+    /// How many times to retry a `44` before giving up.
+    pub max_retries: u8,
 }
 
 type Result<T> = std::result::Result<T, ActorError>;
@@ -44,10 +112,17 @@ type Result<T> = std::result::Result<T, ActorError>;
 impl Default for Actor {
     fn default() -> Self {
         Self {
-            user_agent: None,
-            cert:       None,
-            key:        None,
-            timeout:    Duration::from_secs(5),
+            user_agent:        None,
+            cert:              None,
+            key:               None,
+            timeout:           Duration::from_secs(5),
+            known_hosts:       None,
+            max_redirects:     5,
+            read_timeout:      Duration::from_secs(30),
+            max_response_size: None,
+            identity:          None,
+            identity_for_host: None,
+            rate_limit:        None,
         }
     }
 }
@@ -78,19 +153,84 @@ impl Actor {
         self
     }
 
+    /// Enable TOFU (trust-on-first-use) certificate pinning, backed by a
+    /// known-hosts file at `path`. The file is created on first use; if it
+    /// doesn't exist yet, every host is treated as unseen.
+    pub fn known_hosts(mut self, path: impl Into<PathBuf>) -> Self {
+        self.known_hosts = Some(path.into());
+        self
+    }
+
+    /// Set how many `3x` redirects to transparently follow (default 5).
+    /// Pass 0 to get the raw redirect response back instead.
+    pub fn follow_redirects(mut self, max_redirects: u8) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// Set the deadline for the TLS handshake, header read and body read
+    /// (each timed separately). Default 30 secs.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Cap how many bytes of response body will be buffered before giving up
+    /// with [`ActorError::ResponseTooLarge`]. Default unbounded.
+    pub fn max_response_size(mut self, size: usize) -> Self {
+        self.max_response_size = Some(size);
+        self
+    }
+
+    /// Present `identity` as the client certificate instead of
+    /// [`Actor::cert_file`]/[`Actor::key_file`]. See [`Identity::generate`].
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Choose a client identity per-host instead of a single
+    /// [`Actor::identity`]: called with the request's domain on every
+    /// connection, returning `Some` to present that identity or `None` to
+    /// fall back to [`Actor::cert_file`]/[`Actor::key_file`]. Handy after a
+    /// `60` (client certificate required): generate or look up an identity
+    /// for that host and re-`trot` the same url.
+    pub fn identity_for_host<F>(mut self, selector: F) -> Self
+    where
+        F: Fn(&str) -> Option<Identity> + Send + Sync + 'static,
+    {
+        self.identity_for_host = Some(Arc::new(selector));
+        self
+    }
+
+    /// Opt in to transparently riding out `44` (slow down) responses: sleep
+    /// for the server-requested delay (capped at `max_wait`) and retry, up
+    /// to `max_retries` times, instead of handing the `44` straight back.
+    pub fn rate_limit(mut self, max_wait: Duration, max_retries: u8) -> Self {
+        self.rate_limit = Some(RateLimitConfig {
+            max_wait,
+            max_retries,
+        });
+        self
+    }
+
     /// Send gemini request to url.
     ///
     /// Url can elide the `gemini://` prefix. It's up to you.
+    ///
+    /// `3x` responses are followed automatically, up to
+    /// [`Actor::follow_redirects`]'s limit.
     pub async fn get(&self, url: impl Into<String>) -> Result<Response> {
         let url = self.build_url(url.into(), None)?;
-
-        self.obey_robots(&url).await?;
-        Ok(self.send_request(&url).await?)
+        self.trot_url(url).await
     }
 
     /// Send gemini request to url with input.
     ///
     /// Input is automatically percent-encoded.
+    ///
+    /// `3x` responses are followed automatically, up to
+    /// [`Actor::follow_redirects`]'s limit.
     pub async fn input(
         &self,
         url: impl Into<String>,
@@ -99,33 +239,122 @@ impl Actor {
         let input = input.into();
         let input = urlencoding::encode(&input);
         let url = self.build_url(url.into(), Some(&input))?;
+        self.trot_url(url).await
+    }
 
-        self.obey_robots(&url).await?;
-        Ok(self.send_request(&url).await?)
+    /// (private) Send a request to `url`, transparently following `3x`
+    /// redirects up to `self.max_redirects` hops. Redirect targets are
+    /// resolved against the current url, re-obey robots.txt and re-run the
+    /// TOFU/domain check (since they may point at a different host), and
+    /// loops are refused rather than followed forever. A redirect is also
+    /// refused if it would downgrade the request to a non-gemini scheme. A
+    /// `44` is also transparently retried here, per `self.rate_limit`.
+    async fn trot_url(&self, mut url: Url) -> Result<Response> {
+        let scheme = url.scheme().to_string();
+        let mut visited = vec![url.clone()];
+        let mut hops = 0u8;
+        let mut retries = 0u8;
+
+        loop {
+            self.obey_robots(&url).await?;
+            let response = self.send_request(&url).await?;
+
+            if response.status == 44 {
+                if let Some(cfg) = &self.rate_limit {
+                    if retries < cfg.max_retries {
+                        let wait = response.retry_after().unwrap_or(cfg.max_wait).min(cfg.max_wait);
+                        tokio::time::sleep(wait).await;
+                        retries += 1;
+                        continue;
+                    }
+                    return Err(ActorError::RateLimited {
+                        retry_after: response.retry_after(),
+                    });
+                }
+            }
+
+            if !(30..40).contains(&response.status) {
+                return Ok(response);
+            }
+
+            // `max_redirects == 0` disables redirect-following entirely:
+            // hand back the raw `3x` instead of treating it as exceeding a
+            // limit of zero hops.
+            if self.max_redirects == 0 {
+                return Ok(response);
+            }
+
+            if hops >= self.max_redirects {
+                return Err(ActorError::TooManyRedirects {
+                    chain: visited.iter().map(Url::to_string).collect(),
+                });
+            }
+            hops += 1;
+
+            let next = url.join(&response.meta)?;
+
+            if next.scheme() != scheme {
+                return Err(ActorError::RedirectSchemeChanged {
+                    from: scheme,
+                    to:   next.scheme().to_string(),
+                });
+            }
+
+            if visited.contains(&next) {
+                return Err(ActorError::TooManyRedirects {
+                    chain: visited.iter().map(Url::to_string).collect(),
+                });
+            }
+
+            match response.status {
+                30 => eprintln!("🎠 Following temporary redirect: {url} -> {next}"),
+                31 => eprintln!("🎠 Following permanent redirect: {url} -> {next}"),
+                s => eprintln!("🎠 Following redirect ({s}): {url} -> {next}"),
+            }
+
+            visited.push(next.clone());
+            url = next;
+        }
     }
 
     /// (private) Internal function for sending a request.
     async fn send_request(&self, url: &Url) -> Result<Response> {
+        let domain = url.domain().ok_or(ActorError::DomainErr)?;
+        let port = url.port().unwrap_or(1965);
+
         // Build connector
         let mut connector = SslConnector::builder(SslMethod::tls_client())?;
         connector.set_verify_callback(SslVerifyMode::FAIL_IF_NO_PEER_CERT, |_, _| true);
 
-        // Add client certificate
-        if let Some(key) = &self.key {
-            connector
-                .set_private_key_file(key, SslFiletype::PEM)
-                .map_err(|e| ActorError::KeyCertFileError(e))?;
-        }
-        if let Some(cert) = &self.cert {
-            connector
-                .set_certificate_file(cert, SslFiletype::PEM)
-                .map_err(|e| ActorError::KeyCertFileError(e))?;
+        // Add client certificate: an explicit `self.identity` wins, then
+        // whatever `self.identity_for_host` picks for this domain, then
+        // falling back to file-based cert/key.
+        let identity = self
+            .identity
+            .clone()
+            .or_else(|| self.identity_for_host.as_ref().and_then(|f| f(domain)));
+
+        if let Some(identity) = &identity {
+            let key = PKey::private_key_from_pem(identity.key_pem.as_bytes())
+                .map_err(ActorError::IdentityPemError)?;
+            let cert = X509::from_pem(identity.cert_pem.as_bytes())
+                .map_err(ActorError::IdentityPemError)?;
+            connector.set_private_key(&key)?;
+            connector.set_certificate(&cert)?;
+        } else {
+            if let Some(key) = &self.key {
+                connector
+                    .set_private_key_file(key, SslFiletype::PEM)
+                    .map_err(|e| ActorError::KeyCertFileError(e))?;
+            }
+            if let Some(cert) = &self.cert {
+                connector
+                    .set_certificate_file(cert, SslFiletype::PEM)
+                    .map_err(|e| ActorError::KeyCertFileError(e))?;
+            }
         }
 
         // Connect with tcp
-        let domain = url.domain().ok_or(ActorError::DomainErr)?;
-        let port = url.port().unwrap_or(1965);
-
         let tcp = tokio::time::timeout(
             self.timeout,
             TcpStream::connect(&format!("{domain}:{port}")),
@@ -141,26 +370,35 @@ impl Actor {
 
         let mut stream = SslStream::new(ssl, tcp)?;
 
-        // Write request
-        stream
-            .write_all(&format!("{url}\r\n",).into_bytes())
-            .await?;
+        // Write request (this is also where the TLS handshake happens, since
+        // openssl performs it lazily on first read/write)
+        tokio::time::timeout(
+            self.read_timeout,
+            stream.write_all(&format!("{url}\r\n",).into_bytes()),
+        )
+        .await
+        .map_err(ActorError::Timeout)??;
 
         // Get response header
-        let mut header: Vec<u8> = Vec::new();
-        let mut p = b' ';
-        for _ in 0..=1026 {
-            let c = stream.read_u8().await?;
-
-            // Break if \r\n
-            if p == b'\r' && c == b'\n' {
-                let _ = header.pop();
-                break;
-            }
+        let header = tokio::time::timeout(self.read_timeout, async {
+            let mut header: Vec<u8> = Vec::new();
+            let mut p = b' ';
+            for _ in 0..=1026 {
+                let c = stream.read_u8().await?;
+
+                // Break if \r\n
+                if p == b'\r' && c == b'\n' {
+                    let _ = header.pop();
+                    break;
+                }
 
-            header.push(c);
-            p = c;
-        }
+                header.push(c);
+                p = c;
+            }
+            Ok::<_, std::io::Error>(header)
+        })
+        .await
+        .map_err(ActorError::Timeout)??;
 
         let header = std::str::from_utf8(&header).map_err(|e| ActorError::Utf8Header(e))?;
 
@@ -171,9 +409,28 @@ impl Actor {
             .map_err(|e| ActorError::MalformedStatus(e))?;
         let meta = meta.to_string();
 
-        // Get remaining response content
-        let mut content: Vec<u8> = Vec::new();
-        stream.read_to_end(&mut content).await?;
+        // Get remaining response content, bailing out early if it grows past
+        // `max_response_size`
+        let content = tokio::time::timeout(self.read_timeout, async {
+            let mut content: Vec<u8> = Vec::new();
+            let mut buf = [0u8; 8192];
+            loop {
+                let n = stream.read(&mut buf).await?;
+                if n == 0 {
+                    break;
+                }
+                content.extend_from_slice(&buf[..n]);
+
+                if let Some(limit) = self.max_response_size {
+                    if content.len() > limit {
+                        return Err(ActorError::ResponseTooLarge(limit));
+                    }
+                }
+            }
+            Ok(content)
+        })
+        .await
+        .map_err(ActorError::Timeout)??;
 
         // Get certificate pem
         let certificate = stream
@@ -208,6 +465,11 @@ impl Actor {
             ))?;
         }
 
+        // TOFU pinning (opt-in via `known_hosts`)
+        if let Some(path) = &self.known_hosts {
+            known_hosts::check(path, &format!("{domain}:{port}"), &certificate)?;
+        }
+
         Ok(Response {
             content,
             status,
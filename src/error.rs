@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::{Status, UserAgent};
 
 #[derive(thiserror::Error, Debug)]
@@ -46,6 +48,31 @@ pub enum ActorError {
 
     #[error("Certificate is valid for {0}, not {1}")]
     DomainUncerified(String, String),
+
+    #[error("Certificate for {host} has changed. Expected fingerprint {expected}, got {got}. If this is expected (e.g. the site rotated its certificate), remove the stale entry from your known-hosts file.")]
+    CertificateChanged {
+        host:     String,
+        expected: String,
+        got:      String,
+    },
+
+    #[error("Too many redirects, or a redirect loop: {}", .chain.join(" -> "))]
+    TooManyRedirects { chain: Vec<String> },
+
+    #[error("Refusing to follow a redirect from {from}:// to {to}://")]
+    RedirectSchemeChanged { from: String, to: String },
+
+    #[error("Response exceeded the maximum allowed size of {0} bytes")]
+    ResponseTooLarge(usize),
+
+    #[error("Failed to generate self-signed identity: {0}")]
+    IdentityGeneration(#[from] rcgen::RcgenError),
+
+    #[error("Identity's cert and/or key PEM is malformed")]
+    IdentityPemError(openssl::error::ErrorStack),
+
+    #[error("Still being rate limited after exhausting retries; server last asked to wait {retry_after:?}")]
+    RateLimited { retry_after: Option<Duration> },
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -53,8 +80,15 @@ pub enum ResponseErr {
     #[error("Content isn't utf8: {0}")]
     Utf8Content(std::str::Utf8Error),
 
-    #[error("Expected status {0}, received {1}")]
-    UnexpectedStatus(Status, Status, String),
+    #[error("Expected status {expected}, received {raw}")]
+    UnexpectedStatus {
+        expected: Status,
+        /// The raw status code received, not round-tripped through
+        /// [`Status::from`] (which is lossy for unrecognized in-range
+        /// codes, e.g. `45` collapsing to the generic `TemporaryFailure`).
+        raw:      u8,
+        meta:     String,
+    },
 
     #[error("Expected filetype {0}, receieved {1}")]
     UnexpectedFiletype(String, String),
@@ -70,4 +104,16 @@ pub enum ResponseErr {
 
     #[error("Server's certificate pem is invalid utf-8: {0}")]
     PemInvalidUtf8(std::str::Utf8Error),
+
+    #[error("Failed to compute server's certificate fingerprint: {0}")]
+    CertificateDigest(#[from] openssl::error::ErrorStack),
+
+    #[error("Malformed MIME type in meta: {0}")]
+    MalformedMime(String),
+
+    #[error("Don't know how to decode charset {0}")]
+    UnsupportedCharset(String),
+
+    #[error("Content isn't valid {0}")]
+    InvalidCharsetContent(String),
 }
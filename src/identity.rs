@@ -0,0 +1,42 @@
+use std::{fs, path::PathBuf};
+
+use crate::error::ActorError;
+
+/// An in-memory client (TLS) identity: a self-signed certificate and its
+/// private key, both PEM-encoded.
+///
+/// Many Gemini apps gate content behind client certificates ("accounts").
+/// This lets [`Actor`](crate::Actor) mint an ephemeral one without shelling
+/// out to openssl or touching disk. See [`Actor::identity`](crate::Actor).
+#[derive(Debug, Clone)]
+pub struct Identity {
+    pub cert_pem: String,
+    pub key_pem:  String,
+}
+
+impl Identity {
+    /// Generate a fresh self-signed identity for `common_name`.
+    pub fn generate(common_name: impl Into<String>) -> Result<Self, ActorError> {
+        let cert = rcgen::generate_simple_self_signed(vec![common_name.into()])
+            .map_err(ActorError::IdentityGeneration)?;
+
+        Ok(Self {
+            cert_pem: cert
+                .serialize_pem()
+                .map_err(ActorError::IdentityGeneration)?,
+            key_pem: cert.serialize_private_key_pem(),
+        })
+    }
+
+    /// Write this identity out as `<name>.crt`/`<name>.key`, returning their
+    /// paths.
+    pub fn write_to(&self, name: &str) -> Result<(PathBuf, PathBuf), ActorError> {
+        let cert_path = PathBuf::from(format!("{name}.crt"));
+        let key_path = PathBuf::from(format!("{name}.key"));
+
+        fs::write(&cert_path, &self.cert_pem).map_err(ActorError::Stream)?;
+        fs::write(&key_path, &self.key_pem).map_err(ActorError::Stream)?;
+
+        Ok((cert_path, key_path))
+    }
+}
@@ -0,0 +1,113 @@
+//! TOFU (trust-on-first-use) certificate pinning, backed by a simple
+//! line-based file mapping `host:port` to the fingerprint of the
+//! certificate first seen there. Used by [`crate::Actor::known_hosts`].
+
+use std::{collections::HashMap, path::Path};
+
+use openssl::{hash::MessageDigest, x509::X509};
+
+use crate::{error::ActorError, utils};
+
+type Result<T> = std::result::Result<T, ActorError>;
+
+/// A single pinned entry: the certificate's fingerprint, the algorithm it
+/// was computed with, and the certificate's `not_after` expiry (rendered by
+/// `X509::not_after().to_string()`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pin {
+    pub algorithm:   String,
+    pub fingerprint: String,
+    pub not_after:   String,
+}
+
+/// SHA-256 digest of `cert`'s DER encoding, colon-separated hex.
+pub fn fingerprint(cert: &X509) -> std::result::Result<String, openssl::error::ErrorStack> {
+    let digest = cert.digest(MessageDigest::sha256())?;
+    Ok(digest
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+/// Load `host:port -> Pin` from a known-hosts file. A missing file means no
+/// host has been seen yet.
+pub fn load(path: &Path) -> Result<HashMap<String, Pin>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(ActorError::Stream(e)),
+    };
+
+    Ok(text
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let host_port = parts.next()?;
+            let algorithm = parts.next()?;
+            let fingerprint = parts.next()?;
+            let not_after = parts.collect::<Vec<_>>().join(" ");
+            Some((
+                host_port.to_string(),
+                Pin {
+                    algorithm:   algorithm.to_string(),
+                    fingerprint: fingerprint.to_string(),
+                    not_after,
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Persist `hosts` to `path`, one `host:port algorithm fingerprint not_after`
+/// entry per line.
+pub fn save(path: &Path, hosts: &HashMap<String, Pin>) -> Result<()> {
+    let text = hosts
+        .iter()
+        .map(|(host_port, pin)| {
+            format!(
+                "{host_port} {} {} {}\n",
+                pin.algorithm, pin.fingerprint, pin.not_after
+            )
+        })
+        .collect::<String>();
+
+    std::fs::write(path, text).map_err(ActorError::Stream)
+}
+
+/// Check `cert` against the pinned entry for `host_port` in the known-hosts
+/// file at `path`, recording it on first use and replacing an expired pin,
+/// per TOFU.
+pub fn check(path: &Path, host_port: &str, cert: &X509) -> Result<()> {
+    let fp = fingerprint(cert)?;
+    let mut hosts = load(path)?;
+
+    match hosts.get(host_port) {
+        Some(pin) if pin.fingerprint == fp => Ok(()),
+        Some(pin)
+            if utils::parse_asn1_time(&pin.not_after).is_some_and(|t| t < utils::now_unix()) =>
+        {
+            // The previously pinned certificate has expired; replace the pin.
+            hosts.insert(host_port.to_string(), pin_for(&fp, cert));
+            save(path, &hosts)
+        }
+        Some(pin) => Err(ActorError::CertificateChanged {
+            host:     host_port.to_string(),
+            expected: pin.fingerprint.clone(),
+            got:      fp,
+        }),
+        None => {
+            hosts.insert(host_port.to_string(), pin_for(&fp, cert));
+            save(path, &hosts)
+        }
+    }
+}
+
+/// (private) Build the [`Pin`] to record for a freshly-seen/refreshed cert.
+fn pin_for(fingerprint: &str, cert: &X509) -> Pin {
+    Pin {
+        algorithm:   "sha256".to_string(),
+        fingerprint: fingerprint.to_string(),
+        not_after:   cert.not_after().to_string(),
+    }
+}
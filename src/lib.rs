@@ -2,6 +2,9 @@
 
 mod actor;
 pub mod error;
+mod identity;
+/// TOFU certificate pinning, see [`Actor::known_hosts`]
+pub mod known_hosts;
 /// Provides tools for parsing gemtext
 pub mod parse;
 mod response;
@@ -9,7 +12,8 @@ mod status;
 mod user_agent;
 mod utils;
 
-pub use actor::{trot, trot_in, Actor};
-pub use response::Response;
-pub use status::Status;
+pub use actor::{trot, trot_follow, trot_in, Actor, RateLimitConfig};
+pub use identity::Identity;
+pub use response::{Response, StatusKind};
+pub use status::{Category, InvalidStatusCode, Status};
 pub use user_agent::UserAgent;
@@ -1,9 +1,14 @@
 use clap::Parser;
-use std::{path::PathBuf, process::ExitCode, time::Duration};
+use std::{
+    io::{self, Write as _},
+    path::PathBuf,
+    process::ExitCode,
+    time::Duration,
+};
 use trotter::{
     error::ResponseErr,
     parse::{Gemtext, Symbol},
-    Actor, UserAgent,
+    Actor, Identity, UserAgent,
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -16,12 +21,19 @@ enum TrotErr {
 
     #[error("Expected one of these: archiver, indexer, researcher, webproxy")]
     BadUserAgent,
+
+    #[error("A url is required unless using --gen-identity")]
+    MissingUrl,
+
+    #[error("Failed reading input from stdin: {0}")]
+    Stdin(std::io::Error),
 }
 
 /// 🎠 Trot: A command-line gemini client. Non-success statuses are included in the exit code.
 #[derive(Parser)]
 struct Cli {
-    url: String,
+    /// Not required when using --gen-identity
+    url: Option<String>,
 
     #[clap(short, long)]
     input: Option<String>,
@@ -51,6 +63,11 @@ struct Cli {
     /// Print pretty gemtext responses.
     #[clap(short, long)]
     pretty_print: bool,
+
+    /// Generate a self-signed client identity named <NAME>, writing
+    /// <NAME>.crt/<NAME>.key, instead of making a request.
+    #[clap(long)]
+    gen_identity: Option<String>,
 }
 
 async fn run() -> Result<(), TrotErr> {
@@ -64,8 +81,20 @@ async fn run() -> Result<(), TrotErr> {
         timeout,
         gemtext_only,
         pretty_print,
+        gen_identity,
     } = Cli::parse();
 
+    if let Some(name) = gen_identity {
+        let (cert_path, key_path) = Identity::generate(&name)?.write_to(&name)?;
+        println!(
+            "🎠 Wrote {} and {}",
+            cert_path.display(),
+            key_path.display()
+        );
+        return Ok(());
+    }
+    let url = url.ok_or(TrotErr::MissingUrl)?;
+
     let mut actor = Actor {
         cert,
         key,
@@ -88,13 +117,29 @@ async fn run() -> Result<(), TrotErr> {
         actor.timeout = Duration::from_secs(t);
     }
 
-    // Get response
-    let response = if let Some(input) = input {
-        actor.input(url, input).await?
+    // Get response, transparently prompting for input on 1x responses (as
+    // the protocol intends) until we land on a non-input status.
+    let mut response = if let Some(input) = input {
+        actor.input(url.as_str(), input).await?
     } else {
-        actor.get(url).await?
+        actor.get(url.as_str()).await?
     };
 
+    while let Some(prompt) = response.input_prompt() {
+        print!("{prompt} ");
+        io::stdout().flush().map_err(TrotErr::Stdin)?;
+
+        let answer = if response.input_is_sensitive() {
+            rpassword::read_password().map_err(TrotErr::Stdin)?
+        } else {
+            let mut line = String::new();
+            io::stdin().read_line(&mut line).map_err(TrotErr::Stdin)?;
+            line.trim_end_matches(['\r', '\n']).to_string()
+        };
+
+        response = actor.input(url.as_str(), answer).await?;
+    }
+
     // Save or output
     if let Some(output) = output {
         response.save_to_path(output)?;
@@ -132,9 +177,9 @@ async fn run() -> Result<(), TrotErr> {
 async fn main() -> ExitCode {
     match run().await {
         Err(e) => match e {
-            TrotErr::Response(ResponseErr::UnexpectedStatus(_, status, meta)) => {
+            TrotErr::Response(ResponseErr::UnexpectedStatus { raw, meta, .. }) => {
                 println!("{meta}");
-                ExitCode::from(status.value())
+                ExitCode::from(raw)
             }
             _ => {
                 eprintln!("🎠 Trot error :: {e}");
@@ -12,10 +12,71 @@ pub enum Symbol {
     Codeblock(String, String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Gemtext(pub Vec<Symbol>);
 
 impl Gemtext {
+    /// Start building a gemtext document from scratch.
+    ///
+    /// ```
+    /// # use trotter::parse::Gemtext;
+    /// Gemtext::new()
+    ///     .heading1("Welcome")
+    ///     .text("Hello, capsule.")
+    ///     .link("gemini://example.com", "Example");
+    /// ```
+    pub fn new() -> Self {
+        Gemtext(Vec::new())
+    }
+
+    /// Push a line of plain text.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.0.push(Symbol::Text(text.into()));
+        self
+    }
+
+    /// Push a link line. `label` may be empty.
+    pub fn link(mut self, url: impl Into<String>, label: impl Into<String>) -> Self {
+        self.0.push(Symbol::Link(url.into(), label.into()));
+        self
+    }
+
+    /// Push a level-1 heading.
+    pub fn heading1(mut self, text: impl Into<String>) -> Self {
+        self.0.push(Symbol::Header1(text.into()));
+        self
+    }
+
+    /// Push a level-2 heading.
+    pub fn heading2(mut self, text: impl Into<String>) -> Self {
+        self.0.push(Symbol::Header2(text.into()));
+        self
+    }
+
+    /// Push a level-3 heading.
+    pub fn heading3(mut self, text: impl Into<String>) -> Self {
+        self.0.push(Symbol::Header3(text.into()));
+        self
+    }
+
+    /// Push an unordered list item.
+    pub fn list_item(mut self, text: impl Into<String>) -> Self {
+        self.0.push(Symbol::List(text.into()));
+        self
+    }
+
+    /// Push a quote line.
+    pub fn quote(mut self, text: impl Into<String>) -> Self {
+        self.0.push(Symbol::Quote(text.into()));
+        self
+    }
+
+    /// Push a fenced preformatted block. `body` may contain newlines.
+    pub fn preformatted(mut self, alt: impl Into<String>, body: impl Into<String>) -> Self {
+        self.0.push(Symbol::Codeblock(alt.into(), body.into()));
+        self
+    }
+
     pub fn inner(self) -> Vec<Symbol> {
         self.0
     }
@@ -89,4 +150,167 @@ impl Gemtext {
         }
         Gemtext(v)
     }
+
+    /// Render this document back out as spec-correct `text/gemini`.
+    pub fn to_gemtext(&self) -> String {
+        let mut out = String::new();
+
+        for symbol in &self.0 {
+            match symbol {
+                Symbol::Text(t) => out.push_str(t),
+                Symbol::Link(url, label) => {
+                    out.push_str("=> ");
+                    out.push_str(url);
+                    if !label.is_empty() {
+                        out.push(' ');
+                        out.push_str(label);
+                    }
+                }
+                Symbol::List(t) => {
+                    out.push_str("* ");
+                    out.push_str(t);
+                }
+                Symbol::Quote(t) => {
+                    out.push_str("> ");
+                    out.push_str(t);
+                }
+                Symbol::Header1(t) => {
+                    out.push_str("# ");
+                    out.push_str(t);
+                }
+                Symbol::Header2(t) => {
+                    out.push_str("## ");
+                    out.push_str(t);
+                }
+                Symbol::Header3(t) => {
+                    out.push_str("### ");
+                    out.push_str(t);
+                }
+                Symbol::Codeblock(alt, body) => {
+                    out.push_str("```");
+                    out.push_str(alt);
+                    out.push('\n');
+                    out.push_str(body);
+                    if !body.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```");
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Render this document as semantic HTML. Intended for the `webproxy`
+    /// use case of serving Geminispace over HTTP.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let mut in_list = false;
+
+        for symbol in &self.0 {
+            if in_list && !matches!(symbol, Symbol::List(_)) {
+                out.push_str("</ul>\n");
+                in_list = false;
+            }
+
+            match symbol {
+                Symbol::Text(t) => {
+                    out.push_str("<p>");
+                    out.push_str(&escape_html(t));
+                    out.push_str("</p>\n");
+                }
+                Symbol::Link(url, label) => {
+                    let label = if label.is_empty() { url } else { label };
+                    if is_safe_link_scheme(url) {
+                        out.push_str("<a href=\"");
+                        out.push_str(&escape_html(url));
+                        out.push_str("\">");
+                        out.push_str(&escape_html(label));
+                        out.push_str("</a>\n");
+                    } else {
+                        // Refuse to turn an untrusted `javascript:`/`data:`/etc.
+                        // scheme into a live link; fall back to plain text.
+                        out.push_str("<p>");
+                        out.push_str(&escape_html(label));
+                        out.push_str("</p>\n");
+                    }
+                }
+                Symbol::List(t) => {
+                    if !in_list {
+                        out.push_str("<ul>\n");
+                        in_list = true;
+                    }
+                    out.push_str("<li>");
+                    out.push_str(&escape_html(t));
+                    out.push_str("</li>\n");
+                }
+                Symbol::Quote(t) => {
+                    out.push_str("<blockquote>");
+                    out.push_str(&escape_html(t));
+                    out.push_str("</blockquote>\n");
+                }
+                Symbol::Header1(t) => {
+                    out.push_str("<h1>");
+                    out.push_str(&escape_html(t));
+                    out.push_str("</h1>\n");
+                }
+                Symbol::Header2(t) => {
+                    out.push_str("<h2>");
+                    out.push_str(&escape_html(t));
+                    out.push_str("</h2>\n");
+                }
+                Symbol::Header3(t) => {
+                    out.push_str("<h3>");
+                    out.push_str(&escape_html(t));
+                    out.push_str("</h3>\n");
+                }
+                Symbol::Codeblock(_, body) => {
+                    out.push_str("<pre>");
+                    out.push_str(&escape_html(body));
+                    out.push_str("</pre>\n");
+                }
+            }
+        }
+
+        if in_list {
+            out.push_str("</ul>\n");
+        }
+
+        out
+    }
+}
+
+/// (private) Escape `&`, `<`, `>`, `"` and `'` for safe inclusion in HTML
+/// text content *and* quoted attribute values.
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// (private) Whether `url` is safe to emit as a live `href`: either schemeless
+/// (relative to the capsule) or an explicit `gemini`/`http`/`https` scheme.
+/// Rejects e.g. `javascript:` links from untrusted capsule content.
+fn is_safe_link_scheme(url: &str) -> bool {
+    let Some((scheme, _)) = url.split_once(':') else {
+        return true;
+    };
+
+    let looks_like_scheme = scheme
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+    if !looks_like_scheme {
+        return true;
+    }
+
+    matches!(scheme.to_ascii_lowercase().as_str(), "gemini" | "http" | "https")
 }
@@ -1,8 +1,8 @@
-use std::{fs::File, io::Write, path::PathBuf};
+use std::{collections::HashMap, fs::File, io::Write, path::PathBuf, time::Duration};
 
 use openssl::x509::X509;
 
-use crate::error::ResponseErr;
+use crate::{error::ResponseErr, known_hosts, utils};
 
 /// A gemini response.
 #[derive(Debug)]
@@ -16,15 +16,127 @@ pub struct Response {
 
 type Result<T> = std::result::Result<T, ResponseErr>;
 
+/// Coarse classification of a response, derived from its status's leading
+/// digit. Useful for clients that want to act on "any `4x`" the way the
+/// Gemini spec intends, without enumerating every [`Status`](crate::Status).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusKind {
+    /// 1x. `sensitive` is true for 11, meaning the input shouldn't be echoed.
+    Input { sensitive: bool },
+    /// 2x
+    Success,
+    /// 3x
+    Redirect,
+    /// 4x
+    TempFail,
+    /// 5x
+    PermFail,
+    /// 6x
+    ClientCertRequired,
+    /// Anything outside of 1x-6x.
+    Unknown,
+}
+
+/// A parsed `<META>` MIME media type, e.g. `text/gemini; charset=utf-8`.
+/// Parameter names are lowercased; values are used as-is (quotes stripped).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MimeType {
+    pub type_:   String,
+    pub subtype: String,
+    pub params:  HashMap<String, String>,
+}
+
+impl MimeType {
+    /// `type/subtype`, without parameters.
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.type_, self.subtype)
+    }
+
+    /// (private) Parse a `type/subtype; param=value; ...` string.
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split(';');
+        let (type_, subtype) = parts.next()?.trim().split_once('/')?;
+
+        let mut params = HashMap::new();
+        for part in parts {
+            if let Some((key, value)) = part.trim().split_once('=') {
+                params.insert(
+                    key.trim().to_ascii_lowercase(),
+                    value.trim().trim_matches('"').to_string(),
+                );
+            }
+        }
+
+        Some(Self {
+            type_: type_.trim().to_ascii_lowercase(),
+            subtype: subtype.trim().to_ascii_lowercase(),
+            params,
+        })
+    }
+}
+
 impl Response {
+    /// Classify this response by its status's leading digit. See
+    /// [`StatusKind`].
+    pub fn status_kind(&self) -> StatusKind {
+        match self.status / 10 {
+            1 => StatusKind::Input {
+                sensitive: self.status == 11,
+            },
+            2 => StatusKind::Success,
+            3 => StatusKind::Redirect,
+            4 => StatusKind::TempFail,
+            5 => StatusKind::PermFail,
+            6 => StatusKind::ClientCertRequired,
+            _ => StatusKind::Unknown,
+        }
+    }
+
+    /// If this is a `1x` response, the prompt to present to the user (the
+    /// raw `meta`); `None` otherwise. Feed the user's answer to
+    /// [`Actor::input`](crate::Actor::input) to re-request with it, checking
+    /// [`Response::input_is_sensitive`] first to decide whether to echo it.
+    pub fn input_prompt(&self) -> Option<&str> {
+        matches!(self.status_kind(), StatusKind::Input { .. }).then_some(self.meta.as_str())
+    }
+
+    /// Whether the prompt from [`Response::input_prompt`] asks for sensitive
+    /// input (status `11`) that shouldn't be echoed back to the user. `false`
+    /// on any response that isn't a `1x`.
+    pub fn input_is_sensitive(&self) -> bool {
+        matches!(self.status_kind(), StatusKind::Input { sensitive: true })
+    }
+
+    /// On a `44` (slow down) response, the delay the server asked for before
+    /// retrying, parsed from `meta` as whole seconds. `None` on any other
+    /// status, or if `meta` isn't a valid non-negative integer.
+    pub fn retry_after(&self) -> Option<Duration> {
+        if self.status != 44 {
+            return None;
+        }
+        self.meta.trim().parse::<u64>().ok().map(Duration::from_secs)
+    }
+
+    /// Parse `meta` as a MIME media type. Only meaningful on a `20`
+    /// (success) response; an empty `meta` defaults to `text/gemini;
+    /// charset=utf-8`, per spec.
+    pub fn mime(&self) -> Result<MimeType> {
+        self.require_status(20)?;
+
+        if self.meta.trim().is_empty() {
+            return Ok(MimeType {
+                type_:   "text".to_string(),
+                subtype: "gemini".to_string(),
+                params:  HashMap::from([("charset".to_string(), "utf-8".to_string())]),
+            });
+        }
+
+        MimeType::parse(&self.meta).ok_or_else(|| ResponseErr::MalformedMime(self.meta.clone()))
+    }
+
     /// Returns true if the response is gemtext.
     pub fn is_gemtext(&self) -> bool {
-        if let Some(pos) = self.meta.find("text/gemini") {
-            if pos == 0 {
-                return true;
-            }
-        }
-        false
+        self.mime().is_ok_and(|mime| mime.essence() == "text/gemini")
     }
 
     /// Return gemtext (if any) inside this response.
@@ -41,12 +153,37 @@ impl Response {
         ))
     }
 
-    /// Return utf8 text (if any) inside this response, regardless of mimetype.
+    /// Return text (if any) inside this response, regardless of mimetype.
+    /// Transcodes the body according to the declared `charset` parameter
+    /// (defaulting to utf-8); `utf-8` and `us-ascii` are supported.
     pub fn text(&self) -> Result<String> {
         self.require_status(20)?;
-        Ok(std::str::from_utf8(&self.content)
-            .map_err(|e| ResponseErr::Utf8Content(e))?
-            .to_string())
+
+        let charset = self
+            .mime()?
+            .params
+            .get("charset")
+            .map(|c| c.to_ascii_lowercase())
+            .unwrap_or_else(|| "utf-8".to_string());
+
+        match charset.as_str() {
+            "utf-8" | "utf8" => Ok(std::str::from_utf8(&self.content)
+                .map_err(|e| ResponseErr::Utf8Content(e))?
+                .to_string()),
+            "us-ascii" | "ascii" => {
+                if self.content.iter().any(|b| !b.is_ascii()) {
+                    return Err(ResponseErr::InvalidCharsetContent(charset));
+                }
+                Ok(self.content.iter().map(|&b| b as char).collect())
+            }
+            _ => Err(ResponseErr::UnsupportedCharset(charset)),
+        }
+    }
+
+    /// Return the declared `lang` parameter(s) of a `20` response's MIME
+    /// type, if any.
+    pub fn lang(&self) -> Option<String> {
+        self.mime().ok()?.params.get("lang").cloned()
     }
 
     /// Save response to file.
@@ -68,6 +205,29 @@ impl Response {
         Ok(())
     }
 
+    /// On a `61` (certificate not authorised) or `62` (certificate not
+    /// valid) response, the server's explanation of what was wrong with the
+    /// client certificate that was presented (the raw `meta`); `None`
+    /// otherwise. See [`Actor::identity`](crate::Actor::identity)/
+    /// [`Actor::identity_for_host`](crate::Actor::identity_for_host) for
+    /// presenting one in response to a plain `60`.
+    pub fn certificate_problem(&self) -> Option<&str> {
+        matches!(self.status, 61 | 62).then_some(self.meta.as_str())
+    }
+
+    /// SHA-256 fingerprint of the server's certificate, colon-separated hex.
+    /// This is what [`crate::known_hosts`] pins; compare it yourself to
+    /// implement the standard "accept new cert?" prompt.
+    pub fn certificate_fingerprint(&self) -> Result<String> {
+        known_hosts::fingerprint(&self.certificate).map_err(ResponseErr::CertificateDigest)
+    }
+
+    /// Unix timestamp the server's certificate expires at, or `None` if its
+    /// `not_after` field couldn't be parsed.
+    pub fn certificate_expires_at(&self) -> Option<i64> {
+        utils::parse_asn1_time(&self.certificate.not_after().to_string())
+    }
+
     /// Return the server's certificate pem
     pub fn certificate_pem(&self) -> Result<String> {
         Ok(std::str::from_utf8(
@@ -98,11 +258,11 @@ impl Response {
     /// (private) Error if `s` doesn't match the status
     fn require_status(&self, s: u8) -> Result<()> {
         if self.status != s {
-            Err(ResponseErr::UnexpectedStatus(
-                s.into(),
-                self.status.into(),
-                self.meta.clone(),
-            ))
+            Err(ResponseErr::UnexpectedStatus {
+                expected: s.into(),
+                raw:      self.status,
+                meta:     self.meta.clone(),
+            })
         } else {
             Ok(())
         }
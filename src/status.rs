@@ -53,20 +53,51 @@ pub enum Status {
     /// 61 The supplied client certificate is not authorised for accessing the particular requested resource. The problem is not with the certificate itself, which may be authorised for other resources.
     CertificateNotAuthorised,
 
-    /// 62 The supplied client certificate was not accepted because it is not valid. This indicates a problem with the certificate in and of itself, with no consideration of the particular requested resource. The most likely cause is that the certificate's validity start date is in the future or its expiry date has passed, but this code may also indicate an invalid signature, or a violation of X509 standard requirements. The <META> should provide more information about the exact error.   
+    /// 62 The supplied client certificate was not accepted because it is not valid. This indicates a problem with the certificate in and of itself, with no consideration of the particular requested resource. The most likely cause is that the certificate's validity start date is in the future or its expiry date has passed, but this code may also indicate an invalid signature, or a violation of X509 standard requirements. The <META> should provide more information about the exact error.
     CertificateNotValid,
 
-    /// _ Represents any other unsupported status code
-    BadStatus,
+    /// _ Represents any status code whose leading digit isn't 1-6, i.e. one
+    /// the gemini spec doesn't define any meaning for at all. Stores the raw
+    /// code. Every other, in-spec status code - known or not - is
+    /// represented by one of the variants above; see [`Status::category`].
+    BadStatus(u8),
 }
 
+/// Coarse classification of a [`Status`] by its leading digit. Per the
+/// gemini spec, a client only needs to understand this digit to behave
+/// correctly - it can treat e.g. an unrecognized `4x` exactly like
+/// [`Status::TemporaryFailure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Input,
+    Success,
+    Redirect,
+    TemporaryFailure,
+    PermanentFailure,
+    ClientCertificate,
+    /// A [`Status::BadStatus`], i.e. a code with no leading digit 1-6.
+    Unknown,
+}
+
+/// Error from [`Status::try_from`]: the wrapped code isn't a two-digit
+/// gemini status code with a leading digit of 1-6.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("{0} isn't a valid gemini status code")]
+pub struct InvalidStatusCode(pub u8);
+
 impl std::fmt::Display for Status {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {self:?}", self.value())
+        write!(f, "{} {self:?}", self.code_number())
     }
 }
 
 impl From<u8> for Status {
+    /// Infallible, lossy conversion: a code gemini defines no specific
+    /// variant for maps onto the generic status for its category (e.g. `45`
+    /// becomes [`Status::TemporaryFailure`]) instead of being discarded. Only
+    /// a leading digit outside 1-6 becomes [`Status::BadStatus`]. Prefer
+    /// [`Status::try_from`] when you want to detect that case instead of
+    /// silently falling back.
     fn from(n: u8) -> Self {
         match n {
             10 => Status::Input,
@@ -87,7 +118,26 @@ impl From<u8> for Status {
             60 => Status::ClientCertificateRequired,
             61 => Status::CertificateNotAuthorised,
             62 => Status::CertificateNotValid,
-            _ => Status::BadStatus,
+            10..=19 => Status::Input,
+            20..=29 => Status::Success,
+            30..=39 => Status::RedirectTemporary,
+            40..=49 => Status::TemporaryFailure,
+            50..=59 => Status::PermanentFailure,
+            60..=69 => Status::ClientCertificateRequired,
+            n => Status::BadStatus(n),
+        }
+    }
+}
+
+impl TryFrom<u8> for Status {
+    type Error = InvalidStatusCode;
+
+    /// Like `From<u8>`, but rejects a leading digit outside 1-6 instead of
+    /// producing [`Status::BadStatus`].
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match Status::from(n) {
+            Status::BadStatus(n) => Err(InvalidStatusCode(n)),
+            status => Ok(status),
         }
     }
 }
@@ -99,6 +149,16 @@ impl Status {
     ///
     /// If you use `Status::BadStatus`
     pub fn value(&self) -> u8 {
+        match self {
+            Status::BadStatus(_) => panic!("Hello. You shouldn't be using `Status::BadStatus`. It's meant to be an error entry for numbers that aren't a valid status code."),
+            status => status.code_number(),
+        }
+    }
+
+    /// Return the status number this enum entry represents. Unlike
+    /// [`Status::value`], never panics: a [`Status::BadStatus`] yields back
+    /// the raw code it was built from.
+    pub fn code_number(&self) -> u8 {
         match self {
             Status::Input => 10,
             Status::SensitiveInput => 11,
@@ -118,7 +178,33 @@ impl Status {
             Status::ClientCertificateRequired => 60,
             Status::CertificateNotAuthorised => 61,
             Status::CertificateNotValid => 62,
-            Status::BadStatus => panic!("Hello. You shouldn't be using `Status::BadStatus`. It's meant to be an error entry for numbers that aren't a valid status code."),
+            Status::BadStatus(n) => *n,
+        }
+    }
+
+    /// Classify this status by its leading digit. Never panics: a
+    /// [`Status::BadStatus`] - which by definition has no digit 1-6 to
+    /// classify - yields [`Category::Unknown`] rather than blowing up on a
+    /// code a remote server chose.
+    pub fn category(&self) -> Category {
+        match self {
+            Status::Input | Status::SensitiveInput => Category::Input,
+            Status::Success => Category::Success,
+            Status::RedirectTemporary | Status::RedirectPermanent => Category::Redirect,
+            Status::TemporaryFailure
+            | Status::ServerUnavailable
+            | Status::CgiError
+            | Status::ProxyError
+            | Status::SlowDown => Category::TemporaryFailure,
+            Status::PermanentFailure
+            | Status::NotFound
+            | Status::Gone
+            | Status::ProxyRequestRefused
+            | Status::BadRequest => Category::PermanentFailure,
+            Status::ClientCertificateRequired
+            | Status::CertificateNotAuthorised
+            | Status::CertificateNotValid => Category::ClientCertificate,
+            Status::BadStatus(_) => Category::Unknown,
         }
     }
 }
@@ -1,5 +1,60 @@
 use std::collections::HashMap;
 
+/// Parse an OpenSSL `ASN1_TIME` display string (e.g. `"Jul 27 23:59:59 2026
+/// GMT"`, the format `X509::not_after()` renders as) into a unix timestamp.
+/// Returns `None` if the string isn't in that format.
+pub fn parse_asn1_time(s: &str) -> Option<i64> {
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    let [month, day, time, year, "GMT"] = parts[..] else {
+        return None;
+    };
+
+    let month = match month {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let day: i64 = day.parse().ok()?;
+    let year: i64 = year.parse().ok()?;
+
+    let mut time = time.split(':');
+    let hour: i64 = time.next()?.parse().ok()?;
+    let min: i64 = time.next()?.parse().ok()?;
+    let sec: i64 = time.next()?.parse().ok()?;
+
+    Some(days_from_civil(year, month, day) * 86_400 + hour * 3600 + min * 60 + sec)
+}
+
+/// Current unix timestamp, used to check certificate/pin expiry.
+pub fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Days since the unix epoch for a given (proleptic Gregorian) civil date.
+/// Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
 /// Parse robots.txt file into a hashmap resembling ({"useragent": ["/path1", "/path2"]}`)
 pub fn parse_robots(txt: &str) -> HashMap<&str, Vec<&str>> {
     let mut map: HashMap<&str, Vec<&str>> = HashMap::new();